@@ -0,0 +1,327 @@
+//! A tiny two-pass assembler for writing test programs and examples as
+//! mnemonics instead of raw opcode bytes.
+//!
+//! It shares `cpu::decode`'s opcode table instead of keeping a second copy
+//! by hand, so the assembler and the CPU can never drift apart.
+//!
+//! One instruction (or label definition) per line:
+//!
+//! ```
+//! use nes::assembler::assemble;
+//!
+//! let program = assemble(
+//!     "loop:
+//!      LDA #$01
+//!      STA $20
+//!      BCC loop",
+//! );
+//! ```
+
+use crate::cpu::{self, AddressingMode, Instruction};
+use crate::disasm;
+use std::collections::HashMap;
+
+/// Assembles `src` into raw opcode bytes, resolving labels in a second
+/// pass. Panics on unrecognized mnemonics, operand syntax, or labels -
+/// this is a tool for hand-written test programs, not untrusted input.
+pub fn assemble(src: &str) -> Vec<u8> {
+    let table = OpcodeTable::build();
+    let lines: Vec<Line> = src.lines().filter_map(parse_line).collect();
+
+    let mut labels = HashMap::new();
+    let mut addr: u16 = 0;
+    for line in &lines {
+        match line {
+            Line::Label(name) => {
+                labels.insert(name.clone(), addr);
+            }
+            Line::Instruction { mnemonic, operand } => {
+                addr += instruction_len(&table, mnemonic, operand);
+            }
+        }
+    }
+
+    let mut out = Vec::new();
+    let mut addr: u16 = 0;
+    for line in &lines {
+        let Line::Instruction { mnemonic, operand } = line else {
+            continue;
+        };
+        let len = instruction_len(&table, mnemonic, operand);
+        out.extend(encode(&table, mnemonic, operand, addr + len, &labels));
+        addr += len;
+    }
+    out
+}
+
+/// Mnemonic+addressing-mode -> opcode, built by walking every opcode
+/// `cpu::decode` recognizes rather than keeping a second table by hand
+/// that could fall out of sync.
+struct OpcodeTable(HashMap<(String, AddressingMode), u8>);
+
+impl OpcodeTable {
+    fn build() -> OpcodeTable {
+        let mut table = HashMap::new();
+        for opcode in 0u8..=255 {
+            let (inst, mode) = cpu::decode(opcode);
+            if matches!(inst, Instruction::Illegal(_)) {
+                continue;
+            }
+            table.insert((disasm::mnemonic(&inst).to_string(), mode), opcode);
+        }
+        OpcodeTable(table)
+    }
+
+    fn has_mode(&self, mnemonic: &str, mode: AddressingMode) -> bool {
+        self.0.contains_key(&(mnemonic.to_string(), mode))
+    }
+
+    fn opcode(&self, mnemonic: &str, mode: AddressingMode) -> u8 {
+        *self
+            .0
+            .get(&(mnemonic.to_string(), mode))
+            .unwrap_or_else(|| panic!("{mnemonic} has no {mode:?} addressing mode"))
+    }
+}
+
+enum Line {
+    Label(String),
+    Instruction { mnemonic: String, operand: Operand },
+}
+
+/// Either a literal address/offset or a label to resolve in the second
+/// pass. Zero page vs. absolute is decided at parse time from how many
+/// hex digits were written; a label's width depends on whether the
+/// mnemonic it's attached to takes `Relative` or `Absolute` addressing.
+enum Target {
+    ZeroPage(u8),
+    Absolute(u16),
+    Label(String),
+}
+
+enum Index {
+    X,
+    Y,
+}
+
+enum Operand {
+    None,
+    Accumulator,
+    Immediate(u8),
+    Direct(Target),
+    Indexed(Target, Index),
+    Indirect(u16),
+    IndexedIndirect(u8),
+    IndirectIndexed(u8),
+}
+
+fn parse_line(line: &str) -> Option<Line> {
+    let line = line.trim();
+    if line.is_empty() {
+        return None;
+    }
+    if let Some(label) = line.strip_suffix(':') {
+        return Some(Line::Label(label.to_string()));
+    }
+    let mut parts = line.splitn(2, char::is_whitespace);
+    let mnemonic = parts.next().unwrap().to_uppercase();
+    let operand = parts.next().map(str::trim).filter(|s| !s.is_empty());
+    Some(Line::Instruction {
+        mnemonic,
+        operand: parse_operand(operand),
+    })
+}
+
+fn parse_operand(operand: Option<&str>) -> Operand {
+    let Some(s) = operand else {
+        return Operand::None;
+    };
+    if s == "A" {
+        return Operand::Accumulator;
+    }
+    if let Some(rest) = s.strip_prefix('#') {
+        return Operand::Immediate(parse_u8(rest));
+    }
+    if let Some(inner) = s.strip_prefix('(') {
+        if let Some(inner) = inner.strip_suffix(",X)") {
+            return Operand::IndexedIndirect(parse_u8(inner));
+        }
+        if let Some(inner) = inner.strip_suffix("),Y") {
+            return Operand::IndirectIndexed(parse_u8(inner));
+        }
+        if let Some(inner) = inner.strip_suffix(')') {
+            return Operand::Indirect(parse_u16(inner));
+        }
+        panic!("unrecognized indirect operand: {s}");
+    }
+    if let Some(target) = s.strip_suffix(",X") {
+        return Operand::Indexed(parse_target(target), Index::X);
+    }
+    if let Some(target) = s.strip_suffix(",Y") {
+        return Operand::Indexed(parse_target(target), Index::Y);
+    }
+    Operand::Direct(parse_target(s))
+}
+
+fn parse_target(s: &str) -> Target {
+    match s.strip_prefix('$') {
+        Some(hex) if hex.len() <= 2 => Target::ZeroPage(parse_u8(hex)),
+        Some(hex) => Target::Absolute(parse_u16(hex)),
+        None => Target::Label(s.to_string()),
+    }
+}
+
+fn parse_u8(hex: &str) -> u8 {
+    u8::from_str_radix(hex.trim_start_matches('$'), 16)
+        .unwrap_or_else(|_| panic!("expected an 8-bit hex value, got {hex:?}"))
+}
+
+fn parse_u16(hex: &str) -> u16 {
+    u16::from_str_radix(hex.trim_start_matches('$'), 16)
+        .unwrap_or_else(|_| panic!("expected a 16-bit hex value, got {hex:?}"))
+}
+
+fn instruction_len(table: &OpcodeTable, mnemonic: &str, operand: &Operand) -> u16 {
+    match operand {
+        Operand::None | Operand::Accumulator => 1,
+        Operand::Immediate(_) | Operand::IndexedIndirect(_) | Operand::IndirectIndexed(_) => 2,
+        Operand::Indirect(_) => 3,
+        Operand::Direct(target) => match target {
+            Target::ZeroPage(_) => 2,
+            Target::Absolute(_) => 3,
+            Target::Label(_) => {
+                if table.has_mode(mnemonic, AddressingMode::Relative) {
+                    2
+                } else {
+                    3
+                }
+            }
+        },
+        Operand::Indexed(target, _) => match target {
+            Target::ZeroPage(_) => 2,
+            Target::Absolute(_) | Target::Label(_) => 3,
+        },
+    }
+}
+
+fn resolve_label(labels: &HashMap<String, u16>, name: &str) -> u16 {
+    *labels
+        .get(name)
+        .unwrap_or_else(|| panic!("undefined label: {name}"))
+}
+
+fn encode(
+    table: &OpcodeTable,
+    mnemonic: &str,
+    operand: &Operand,
+    pc_after: u16,
+    labels: &HashMap<String, u16>,
+) -> Vec<u8> {
+    use AddressingMode::*;
+    match operand {
+        Operand::None => vec![table.opcode(mnemonic, Implicit)],
+        Operand::Accumulator => vec![table.opcode(mnemonic, Accumulator)],
+        Operand::Immediate(v) => vec![table.opcode(mnemonic, Immediate), *v],
+        Operand::IndexedIndirect(zp) => vec![table.opcode(mnemonic, IndexedIndirect), *zp],
+        Operand::IndirectIndexed(zp) => vec![table.opcode(mnemonic, IndirectIndexed), *zp],
+        Operand::Indirect(addr) => {
+            let mut bytes = vec![table.opcode(mnemonic, Indirect)];
+            bytes.extend(addr.to_le_bytes());
+            bytes
+        }
+        Operand::Direct(target) => match target {
+            Target::ZeroPage(addr) => vec![table.opcode(mnemonic, ZeroPage), *addr],
+            Target::Absolute(addr) => {
+                let mut bytes = vec![table.opcode(mnemonic, Absolute)];
+                bytes.extend(addr.to_le_bytes());
+                bytes
+            }
+            Target::Label(name) => {
+                let target_addr = resolve_label(labels, name);
+                if table.has_mode(mnemonic, Relative) {
+                    let offset = target_addr.wrapping_sub(pc_after) as u8;
+                    vec![table.opcode(mnemonic, Relative), offset]
+                } else {
+                    let mut bytes = vec![table.opcode(mnemonic, Absolute)];
+                    bytes.extend(target_addr.to_le_bytes());
+                    bytes
+                }
+            }
+        },
+        Operand::Indexed(target, index) => {
+            let (zp_mode, abs_mode) = match index {
+                Index::X => (ZeroPageX, AbsoluteX),
+                Index::Y => (ZeroPageY, AbsoluteY),
+            };
+            match target {
+                Target::ZeroPage(addr) => vec![table.opcode(mnemonic, zp_mode), *addr],
+                Target::Absolute(addr) => {
+                    let mut bytes = vec![table.opcode(mnemonic, abs_mode)];
+                    bytes.extend(addr.to_le_bytes());
+                    bytes
+                }
+                Target::Label(name) => {
+                    let addr = resolve_label(labels, name);
+                    let mut bytes = vec![table.opcode(mnemonic, abs_mode)];
+                    bytes.extend(addr.to_le_bytes());
+                    bytes
+                }
+            }
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn assembles_immediate_and_zero_page() {
+        assert_eq!(assemble("LDA #$01"), vec![0xA9, 0x01]);
+        assert_eq!(assemble("STA $20"), vec![0x85, 0x20]);
+    }
+
+    #[test]
+    fn assembles_absolute_and_indexed() {
+        assert_eq!(assemble("LDA $2000"), vec![0xAD, 0x00, 0x20]);
+        assert_eq!(assemble("LDA $2000,X"), vec![0xBD, 0x00, 0x20]);
+        assert_eq!(assemble("LDA $20,X"), vec![0xB5, 0x20]);
+    }
+
+    #[test]
+    fn assembles_indirect_modes() {
+        assert_eq!(assemble("LDA ($20,X)"), vec![0xA1, 0x20]);
+        assert_eq!(assemble("LDA ($20),Y"), vec![0xB1, 0x20]);
+        assert_eq!(assemble("JMP ($2000)"), vec![0x6C, 0x00, 0x20]);
+    }
+
+    #[test]
+    fn assembles_implicit_and_accumulator() {
+        assert_eq!(assemble("CLC"), vec![0x18]);
+        assert_eq!(assemble("ASL A"), vec![0x0A]);
+    }
+
+    #[test]
+    fn resolves_a_forward_jmp_label() {
+        let program = assemble(
+            "JMP skip
+             LDA #$01
+             skip:
+             NOP",
+        );
+        assert_eq!(program, vec![0x4C, 0x05, 0x00, 0xA9, 0x01, 0xEA]);
+    }
+
+    #[test]
+    fn resolves_a_backward_branch_label() {
+        // loop: (addr 0) NOP (addr 0, 1 byte) BCC loop (addr 1, 2 bytes)
+        // BCC's operand is the signed offset from the byte after it (addr 3)
+        // back to the label (addr 0), i.e. -3 = 0xFD.
+        let program = assemble(
+            "loop:
+             NOP
+             BCC loop",
+        );
+        assert_eq!(program, vec![0xEA, 0x90, 0xFD]);
+    }
+}
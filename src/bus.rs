@@ -0,0 +1,112 @@
+use crate::memory::Memory;
+
+// 2KB of internal work RAM, mirrored up to $1FFF.
+const RAM_SIZE: usize = 0x0800;
+const RAM_END: u16 = 0x1FFF;
+const RAM_MIRROR_MASK: u16 = (RAM_SIZE - 1) as u16;
+
+// not the real size of a rom, just for now
+pub const ROM_SIZE: usize = 0x0F00;
+// PRG ROM is mapped to the top of the address space so that the
+// RESET/NMI/IRQ vectors at $FFFA-$FFFF always land inside it.
+pub const ROM_START: u16 = (0x10000 - ROM_SIZE) as u16;
+
+/// An address space the CPU reads and writes through.
+///
+/// Pulling this out of `CPU` means the CPU doesn't need to know whether an
+/// address lands in RAM, ROM, or (eventually) a memory-mapped peripheral
+/// like the PPU or APU, and makes stores observable instead of vanishing
+/// into a field the rest of the system can't see.
+pub trait Bus {
+    fn read_u8(&self, addr: u16) -> u8;
+    fn write_u8(&mut self, addr: u16, val: u8);
+
+    /// Little-endian 16-bit read, used for addresses and vectors.
+    fn read_u16(&self, addr: u16) -> u16 {
+        let lo = self.read_u8(addr) as u16;
+        let hi = self.read_u8(addr.wrapping_add(1)) as u16;
+        (hi << 8) | lo
+    }
+}
+
+/// The default NES CPU memory map: 2KB of internal RAM mirrored up to
+/// $1FFF, and a PRG ROM region occupying the top of the address space.
+#[derive(Debug)]
+pub struct CpuBus {
+    ram: Memory<RAM_SIZE>,
+    rom: Memory<ROM_SIZE>,
+}
+
+impl CpuBus {
+    pub fn new(rom: Memory<ROM_SIZE>) -> CpuBus {
+        CpuBus {
+            ram: Memory::new(),
+            rom,
+        }
+    }
+}
+
+impl Bus for CpuBus {
+    fn read_u8(&self, addr: u16) -> u8 {
+        match addr {
+            0x0000..=RAM_END => self.ram.read_u8(addr & RAM_MIRROR_MASK),
+            _ if addr >= ROM_START => self.rom.read_u8(addr - ROM_START),
+            _ => 0,
+        }
+    }
+
+    fn write_u8(&mut self, addr: u16, val: u8) {
+        match addr {
+            0x0000..=RAM_END => self.ram.write_u8(addr & RAM_MIRROR_MASK, val),
+            _ if addr >= ROM_START => self.rom.write_u8(addr - ROM_START, val),
+            _ => {}
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn bus() -> CpuBus {
+        CpuBus::new(Memory::new())
+    }
+
+    #[test]
+    fn test_ram_mirror() {
+        let mut bus = bus();
+        bus.write_u8(0x0000, 0x42);
+        assert_eq!(bus.read_u8(0x0800), 0x42);
+        assert_eq!(bus.read_u8(0x1000), 0x42);
+        assert_eq!(bus.read_u8(0x1800), 0x42);
+    }
+
+    #[test]
+    fn test_ram_mirror_write_through_mirror() {
+        let mut bus = bus();
+        bus.write_u8(0x0801, 0x99);
+        assert_eq!(bus.read_u8(0x0001), 0x99);
+    }
+
+    #[test]
+    fn test_rom_boundary() {
+        let mut bus = bus();
+        bus.write_u8(ROM_START, 0x55);
+        assert_eq!(bus.read_u8(ROM_START), 0x55);
+        assert_eq!(bus.read_u8(0xFFFF), 0x00);
+    }
+
+    #[test]
+    fn test_rom_below_start_is_unmapped() {
+        let mut bus = bus();
+        bus.write_u8(ROM_START - 1, 0x55);
+        assert_eq!(bus.read_u8(ROM_START - 1), 0x00);
+    }
+
+    #[test]
+    fn test_unmapped_region_reads_as_zero() {
+        let bus = bus();
+        assert_eq!(bus.read_u8(RAM_END.wrapping_add(1)), 0x00);
+        assert_eq!(bus.read_u8(0x5000), 0x00);
+    }
+}
@@ -1,13 +1,9 @@
 use bitflags::bitflags;
-use crate::memory::Memory;
-
-// 2KB working RAM for the CPU
-const WRAM_SIZE: usize = 0x0800;
-// not the real size of a rom, just for now
-const ROM_SIZE: usize = 0x0F00;
+use crate::bus::Bus;
+use crate::disasm;
 
 /// see https://www.nesdev.org/obelisk-6502-guide/addressing.html
-#[derive(Debug)]
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash)]
 pub enum AddressingMode {
     Implicit,
     Accumulator,
@@ -24,6 +20,17 @@ pub enum AddressingMode {
     IndirectIndexed,
 }
 
+/// The result of resolving an [`AddressingMode`]: either a value the
+/// instruction operates on directly, or a 16-bit effective address it
+/// should read/write through the bus.
+#[derive(Debug)]
+enum OpInput {
+    Implied,
+    Accumulator,
+    Immediate(u8),
+    Address(u16),
+}
+
 bitflags! {
     /// see https://www.nesdev.org/obelisk-6502-guide/registers.html
     #[derive(Debug, Copy, Clone, PartialEq)]
@@ -48,30 +55,38 @@ impl Status {
 
 #[derive(Debug)]
 #[allow(clippy::upper_case_acronyms)]
-// FIXME: only zero-page access currently supported
-enum Instruction {
-    ADC(u8),
-    AND(u8),
-    ASL(u8),
+pub(crate) enum Instruction {
+    ADC,
+    AND,
+    ASL,
     BRK,
-    BCC(u8),
+    BCC,
     CLC,
     CLD,
     CLI,
     CLV,
     DEX,
     DEY,
-    INC(u8),
+    INC,
     INX,
     INY,
-    LDA(u8), // Immediate
-    LDX(u8), // Immediate
-    LDY(u8), // Immediate
+    JMP,
+    JSR,
+    LDA,
+    LDX,
+    LDY,
     NOP,
+    PHA,
+    PHP,
+    PLA,
+    PLP,
+    RTI,
+    RTS,
+    SBC,
     SEC,
     SED,
     SEI,
-    STA(u8),
+    STA,
     TAX,
     TAY,
     TXA,
@@ -81,7 +96,7 @@ enum Instruction {
 
 // naming conventions from https://www.masswerk.at/6502/6502_instruction_set.html
 #[derive(Debug)]
-pub struct CPU {
+pub struct CPU<B: Bus> {
     // acccumulator
     acc: u8,
     // X register
@@ -94,13 +109,11 @@ pub struct CPU {
     sp: u8,
     // program counter
     pc: u16,
-    wram: Memory<WRAM_SIZE>,
-    rom: Memory<ROM_SIZE>,
+    bus: B,
 }
 
-impl CPU {
-    pub fn new(rom: Memory<ROM_SIZE>) -> CPU {
-        let wram = Memory::new();
+impl<B: Bus> CPU<B> {
+    pub fn new(bus: B) -> CPU<B> {
         CPU {
             acc: 0,
             x: 0,
@@ -108,79 +121,243 @@ impl CPU {
             sr: Status::U & Status::I,
             sp: 0xFD,
             pc: 0,
-            rom,
-            wram,
+            bus,
         }
     }
 
     fn fetch(&mut self) -> u8 {
-        let opcode = self.rom.read_u8(self.pc);
-        self.pc += 1;
+        let opcode = self.bus.read_u8(self.pc);
+        self.pc = self.pc.wrapping_add(1);
         opcode
     }
 
+    fn fetch_u16(&mut self) -> u16 {
+        let lo = self.fetch();
+        let hi = self.fetch();
+        u16::from_le_bytes([lo, hi])
+    }
+
+    // reads a little-endian pointer out of zero page, wrapping within
+    // the page instead of crossing into page one
+    fn read_zero_page_u16(&self, ptr: u8) -> u16 {
+        read_indirect_u16(&self.bus, ptr as u16)
+    }
+
+    // the stack lives in page one ($0100-$01FF) and grows downwards
+    fn push_u8(&mut self, val: u8) {
+        self.bus.write_u8(0x0100 + self.sp as u16, val);
+        self.sp = self.sp.wrapping_sub(1);
+    }
+
+    fn pull_u8(&mut self) -> u8 {
+        self.sp = self.sp.wrapping_add(1);
+        self.bus.read_u8(0x0100 + self.sp as u16)
+    }
+
+    fn push_u16(&mut self, val: u16) {
+        self.push_u8((val >> 8) as u8);
+        self.push_u8(val as u8);
+    }
+
+    fn pull_u16(&mut self) -> u16 {
+        let lo = self.pull_u8() as u16;
+        let hi = self.pull_u8() as u16;
+        (hi << 8) | lo
+    }
+
+    // pushes PC and status (B set per `b_flag`, on the pushed copy only) and jumps
+    // through `vector`, the shared tail of BRK/IRQ/NMI
+    fn interrupt(&mut self, vector: u16, b_flag: bool) {
+        self.push_u16(self.pc);
+        let mut pushed = self.sr;
+        pushed.set(Status::B, b_flag);
+        pushed.set(Status::U, true);
+        self.push_u8(pushed.bits());
+        self.sr.set(Status::I, true);
+        self.pc = self.bus.read_u16(vector);
+    }
+
+    /// Triggers a non-maskable interrupt, vectored through `$FFFA`.
+    pub fn nmi(&mut self) {
+        self.interrupt(0xFFFA, false);
+    }
+
+    /// Triggers a maskable interrupt, vectored through `$FFFE`. Ignored
+    /// while `Status::I` is set.
+    pub fn irq(&mut self) {
+        if self.sr.contains(Status::I) {
+            return;
+        }
+        self.interrupt(0xFFFE, false);
+    }
+
+    /// Resets the CPU, vectored through `$FFFC`, as if the system had
+    /// just powered on.
+    pub fn reset(&mut self) {
+        self.sp = self.sp.wrapping_sub(3);
+        self.sr.set(Status::I, true);
+        self.pc = self.bus.read_u16(0xFFFC);
+    }
+
+    // turns the addressing mode into a resolved value/address, fetching
+    // however many operand bytes that mode needs along the way
+    fn resolve(&mut self, mode: AddressingMode) -> OpInput {
+        use AddressingMode::*;
+        match mode {
+            Implicit => OpInput::Implied,
+            Accumulator => OpInput::Accumulator,
+            Immediate => OpInput::Immediate(self.fetch()),
+            ZeroPage => OpInput::Address(self.fetch() as u16),
+            ZeroPageX => OpInput::Address(self.fetch().wrapping_add(self.x) as u16),
+            ZeroPageY => OpInput::Address(self.fetch().wrapping_add(self.y) as u16),
+            Relative => {
+                let offset = self.fetch() as i8;
+                OpInput::Address(self.pc.wrapping_add(offset as u16))
+            }
+            Absolute => OpInput::Address(self.fetch_u16()),
+            AbsoluteX => OpInput::Address(self.fetch_u16().wrapping_add(self.x as u16)),
+            AbsoluteY => OpInput::Address(self.fetch_u16().wrapping_add(self.y as u16)),
+            Indirect => {
+                let ptr = self.fetch_u16();
+                OpInput::Address(read_indirect_u16(&self.bus, ptr))
+            }
+            IndexedIndirect => {
+                let ptr = self.fetch().wrapping_add(self.x);
+                OpInput::Address(self.read_zero_page_u16(ptr))
+            }
+            IndirectIndexed => {
+                let ptr = self.fetch();
+                let base = self.read_zero_page_u16(ptr);
+                OpInput::Address(base.wrapping_add(self.y as u16))
+            }
+        }
+    }
+
     // may step PC if opcode requires data
-    fn decode(&mut self, opcode: u8) -> Instruction {
-        use Instruction::*;
-        match opcode {
-            0x00 => BRK,
-            0x06 => ASL(self.fetch()),
-            0x18 => CLC,
-            0x25 => AND(self.fetch()),
-            0x38 => SEC,
-            0x58 => CLI,
-            0x65 => ADC(self.fetch()),
-            0x78 => SEI,
-            0x85 => STA(self.fetch()),
-            0x88 => DEY,
-            0x8A => TXA,
-            0x90 => BCC(self.fetch()),
-            0x98 => TYA,
-            0xA4 => LDY(self.fetch()),
-            0xA6 => LDX(self.fetch()),
-            0xA8 => TAY,
-            0xA9 => LDA(self.fetch()),
-            0xAA => TAX,
-            0xB8 => CLV,
-            0xC8 => INY,
-            0xCA => DEX,
-            0xD8 => CLD,
-            0xE6 => INC(self.fetch()),
-            0xE8 => INX,
-            0xEA => NOP,
-            0xF8 => SED,
-            _ => Illegal(opcode)
+    fn decode(&self, opcode: u8) -> (Instruction, AddressingMode) {
+        decode(opcode)
+    }
+
+    fn operand_value(&self, input: &OpInput) -> u8 {
+        match *input {
+            OpInput::Immediate(v) => v,
+            OpInput::Accumulator => self.acc,
+            OpInput::Address(addr) => self.bus.read_u8(addr),
+            OpInput::Implied => unreachable!("instruction has no operand to read"),
+        }
+    }
+
+    fn operand_address(&self, input: &OpInput) -> u16 {
+        match *input {
+            OpInput::Address(addr) => addr,
+            _ => unreachable!("instruction expected a resolved address"),
+        }
+    }
+
+    fn add_with_carry(&mut self, val: u8) {
+        let carry = self.sr.contains(Status::C) as u16;
+        if self.sr.contains(Status::D) {
+            let al = (self.acc as u16 & 0x0F) + (val as u16 & 0x0F) + carry;
+            let al = if al > 9 { al + 6 } else { al };
+            let ah = (self.acc as u16 >> 4) + (val as u16 >> 4) + if al > 0x0F { 1 } else { 0 };
+
+            // N/V are set from the intermediate result, before the high
+            // nibble gets its own decimal correction below
+            let intermediate = (((ah << 4) | (al & 0x0F)) & 0xFF) as u8;
+            self.sr.set(Status::N, intermediate & 0x80 != 0);
+            self.sr
+                .set(Status::V, (self.acc ^ intermediate) & (val ^ intermediate) & 0x80 != 0);
+
+            let ah = if ah > 9 { ah + 6 } else { ah };
+            self.sr.set(Status::C, ah > 0x0F);
+
+            // Z still comes from the binary sum, a quirk of the real chip
+            let binary_sum = self.acc as u16 + val as u16 + carry;
+            self.sr.set(Status::Z, binary_sum as u8 == 0);
+
+            self.acc = (((ah << 4) | (al & 0x0F)) & 0xFF) as u8;
+        } else {
+            let sum = self.acc as u16 + val as u16 + carry;
+            let result = sum as u8;
+            self.sr.set(Status::C, sum > 0xFF);
+            self.sr
+                .set(Status::V, (self.acc ^ result) & (val ^ result) & 0x80 != 0);
+            self.acc = result;
+            self.sr.set_zn_flags(result);
         }
     }
 
-    fn execute(&mut self, inst: Instruction) {
+    // SBC has its own decimal correction rather than reusing
+    // `add_with_carry` on a flipped operand: BCD subtraction corrects by
+    // subtracting 6 on a nibble borrow, which isn't the same arithmetic as
+    // ADC's "add 6 on a nibble carry" run on the one's complement. N/V/Z/C
+    // still come from the binary (one's-complement) subtraction regardless
+    // of decimal mode, a quirk of the real chip shared with decimal ADC.
+    fn subtract_with_carry(&mut self, val: u8) {
+        let carry = self.sr.contains(Status::C) as u16;
+
+        let flipped = val ^ 0xFF;
+        let sum = self.acc as u16 + flipped as u16 + carry;
+        let binary_result = sum as u8;
+        self.sr.set(Status::C, sum > 0xFF);
+        self.sr
+            .set(Status::V, (self.acc ^ binary_result) & (flipped ^ binary_result) & 0x80 != 0);
+        self.sr.set_zn_flags(binary_result);
+
+        if self.sr.contains(Status::D) {
+            let acc = self.acc as i16;
+            let m = val as i16;
+            let mut al = (acc & 0x0F) - (m & 0x0F) + carry as i16 - 1;
+            let mut ah = (acc >> 4) - (m >> 4);
+            if al < 0 {
+                al -= 6;
+                ah -= 1;
+            }
+            if ah < 0 {
+                ah -= 6;
+            }
+            self.acc = (((ah << 4) & 0xF0) | (al & 0x0F)) as u8;
+        } else {
+            self.acc = binary_result;
+        }
+    }
+
+    fn execute(&mut self, inst: Instruction, input: OpInput) {
         use Instruction::*;
         match inst {
             BRK => {
-                // TODO
-                // loop forever until we come up with a better
-                // way of handling this
-                todo!("interrupts");
+                // BRK is a 1-byte opcode but the real 6502 still fetches
+                // (and discards) a padding byte after it, so the pushed
+                // return address ends up as PC+2
+                self.fetch();
+                self.interrupt(0xFFFE, true);
             }
-            ASL(addr) => {
-                let data = self.wram.read_u8(addr as u16);
+            ASL => {
+                let data = match input {
+                    OpInput::Accumulator => self.acc,
+                    OpInput::Address(addr) => self.bus.read_u8(addr),
+                    _ => unreachable!("ASL operand must be accumulator or memory"),
+                };
                 self.sr.set(Status::C, (data >> 7) & 1 > 0);
-                let x = data.wrapping_shl(1);
-                self.sr.set_zn_flags(x);
-                self.wram.write_u8(addr as u16, x);
+                let result = data.wrapping_shl(1);
+                self.sr.set_zn_flags(result);
+                match input {
+                    OpInput::Accumulator => self.acc = result,
+                    OpInput::Address(addr) => self.bus.write_u8(addr, result),
+                    _ => unreachable!(),
+                }
             }
-            AND(addr) => {
-                let data = self.wram.read_u8(addr as u16);
-                self.acc &= data;
+            AND => {
+                self.acc &= self.operand_value(&input);
                 self.sr.set_zn_flags(self.acc);
             }
-            ADC(addr) => {
-                let data = self.wram.read_u8(addr as u16);
-                let (x, o) = self.acc.overflowing_add(data);
-                self.acc = x;
-                self.sr.set_zn_flags(self.acc);
-                self.sr.set(Status::C, o);
-                // TODO: overflow flag
+            ADC => {
+                let data = self.operand_value(&input);
+                self.add_with_carry(data);
+            }
+            SBC => {
+                let data = self.operand_value(&input);
+                self.subtract_with_carry(data);
             }
             CLC => {
                 self.sr.set(Status::C, false);
@@ -202,8 +379,8 @@ impl CPU {
                 self.y = self.y.wrapping_sub(1);
                 self.sr.set_zn_flags(self.y);
             }
-            LDA(data) => {
-                self.acc = data;
+            LDA => {
+                self.acc = self.operand_value(&input);
                 self.sr.set_zn_flags(self.acc);
             }
             SEC => {
@@ -215,29 +392,70 @@ impl CPU {
             SEI => {
                 self.sr.set(Status::I, true);
             }
-            STA(addr) => {
-                self.wram.write_u8(addr as u16, self.acc)
+            STA => {
+                self.bus.write_u8(self.operand_address(&input), self.acc)
             }
-            BCC(offset) => {
+            BCC => {
                 if self.sr.contains(Status::C) {
-                    self.pc = self.pc.wrapping_add(offset as u16);
+                    self.pc = self.operand_address(&input);
                 }
             }
-            INC(addr) => {
-                let data = self.wram.read_u8(addr as u16);
-                let x = data.wrapping_add(1);
-                self.wram.write_u8(addr as u16, x);
-                self.sr.set_zn_flags(x);
+            INC => {
+                let addr = self.operand_address(&input);
+                let data = self.bus.read_u8(addr);
+                let result = data.wrapping_add(1);
+                self.bus.write_u8(addr, result);
+                self.sr.set_zn_flags(result);
+            }
+            JMP => {
+                self.pc = self.operand_address(&input);
             }
-            LDX(data) => {
-                self.x = data;
+            JSR => {
+                let addr = self.operand_address(&input);
+                // points at the last byte of the JSR instruction, not the
+                // next one, per the 6502's RTS convention
+                let return_addr = self.pc.wrapping_sub(1);
+                self.push_u16(return_addr);
+                self.pc = addr;
+            }
+            LDX => {
+                self.x = self.operand_value(&input);
                 self.sr.set_zn_flags(self.x);
             }
-            LDY(data) => {
-                self.y = data;
+            LDY => {
+                self.y = self.operand_value(&input);
                 self.sr.set_zn_flags(self.y);
             }
             NOP => {}
+            PHA => {
+                self.push_u8(self.acc);
+            }
+            PHP => {
+                let mut pushed = self.sr;
+                pushed.set(Status::B, true);
+                pushed.set(Status::U, true);
+                self.push_u8(pushed.bits());
+            }
+            PLA => {
+                self.acc = self.pull_u8();
+                self.sr.set_zn_flags(self.acc);
+            }
+            PLP => {
+                let bits = self.pull_u8();
+                self.sr = Status::from_bits_truncate(bits);
+                self.sr.remove(Status::B);
+                self.sr.insert(Status::U);
+            }
+            RTI => {
+                let bits = self.pull_u8();
+                self.sr = Status::from_bits_truncate(bits);
+                self.sr.remove(Status::B);
+                self.sr.insert(Status::U);
+                self.pc = self.pull_u16();
+            }
+            RTS => {
+                self.pc = self.pull_u16().wrapping_add(1);
+            }
             INX => {
                 self.x = self.x.wrapping_add(1);
                 self.sr.set_zn_flags(self.x);
@@ -268,30 +486,208 @@ impl CPU {
 
     pub fn tick(&mut self) {
         let opcode = self.fetch();
-        let inst = self.decode(opcode);
-        self.execute(inst);
+        let (inst, mode) = self.decode(opcode);
+        let input = self.resolve(mode);
+        self.execute(inst, input);
+    }
+
+    /// The current value of the program counter.
+    pub fn pc(&self) -> u16 {
+        self.pc
+    }
+
+    /// Renders the instruction about to run as one nestest-style trace
+    /// line: `PC`, the raw opcode bytes, the disassembled mnemonic and
+    /// operand, then the register file. Reads the bus but never mutates
+    /// CPU state, so it's safe to call before every `tick` without
+    /// affecting emulation.
+    pub fn trace(&self) -> String {
+        let opcode = self.bus.read_u8(self.pc);
+        let (inst, mode) = decode(opcode);
+        let len = disasm::operand_len(&mode);
+
+        let mut raw_bytes = Vec::with_capacity(1 + len as usize);
+        raw_bytes.push(opcode);
+        for i in 0..len {
+            raw_bytes.push(self.bus.read_u8(self.pc.wrapping_add(1 + i)));
+        }
+        let pc_after_operand = self.pc.wrapping_add(1 + len);
+        let operand_str = disasm::format_operand(
+            &mode,
+            pc_after_operand,
+            &raw_bytes[1..],
+            self.x,
+            self.y,
+            &self.bus,
+        );
+
+        let hex_bytes = raw_bytes
+            .iter()
+            .map(|b| format!("{:02X}", b))
+            .collect::<Vec<_>>()
+            .join(" ");
+
+        format!(
+            "{:04X}  {:<8}  {} {:<27} A:{:02X} X:{:02X} Y:{:02X} P:{:02X} SP:{:02X}",
+            self.pc,
+            hex_bytes,
+            disasm::mnemonic(&inst),
+            operand_str,
+            self.acc,
+            self.x,
+            self.y,
+            self.sr.bits(),
+            self.sp
+        )
+    }
+}
+
+// reads a little-endian pointer off the bus, wrapping the high byte's
+// address within the same page instead of crossing into the next one: if
+// `ptr` is e.g. $10FF the high byte is (incorrectly, but per real 6502
+// hardware) read from $1000 instead of $1100. For a zero-page `ptr` (high
+// byte $00) this is exactly the zero-page wraparound used by
+// `IndexedIndirect`/`IndirectIndexed`. A free function rather than a
+// `CPU` method so `disasm::format_operand` can share this exact
+// arithmetic instead of keeping its own copy in sync by hand.
+pub(crate) fn read_indirect_u16(bus: &impl Bus, ptr: u16) -> u16 {
+    let lo = bus.read_u8(ptr);
+    let hi_addr = (ptr & 0xFF00) | (ptr as u8).wrapping_add(1) as u16;
+    let hi = bus.read_u8(hi_addr);
+    u16::from_le_bytes([lo, hi])
+}
+
+/// Maps an opcode byte to the instruction and addressing mode it selects.
+/// A free function (rather than a `CPU` method) so the assembler can
+/// share this exact table instead of keeping its own copy in sync by hand.
+pub(crate) fn decode(opcode: u8) -> (Instruction, AddressingMode) {
+    use Instruction::*;
+    use AddressingMode::*;
+    match opcode {
+        0x00 => (BRK, Implicit),
+        0x06 => (ASL, ZeroPage),
+        0x08 => (PHP, Implicit),
+        0x0A => (ASL, Accumulator),
+        0x0E => (ASL, Absolute),
+        0x16 => (ASL, ZeroPageX),
+        0x18 => (CLC, Implicit),
+        0x20 => (JSR, Absolute),
+        0x28 => (PLP, Implicit),
+        0x1E => (ASL, AbsoluteX),
+        0x21 => (AND, IndexedIndirect),
+        0x25 => (AND, ZeroPage),
+        0x29 => (AND, Immediate),
+        0x2D => (AND, Absolute),
+        0x31 => (AND, IndirectIndexed),
+        0x35 => (AND, ZeroPageX),
+        0x38 => (SEC, Implicit),
+        0x39 => (AND, AbsoluteY),
+        0x3D => (AND, AbsoluteX),
+        0x40 => (RTI, Implicit),
+        0x48 => (PHA, Implicit),
+        0x4C => (JMP, Absolute),
+        0x58 => (CLI, Implicit),
+        0x60 => (RTS, Implicit),
+        0x68 => (PLA, Implicit),
+        0x61 => (ADC, IndexedIndirect),
+        0x65 => (ADC, ZeroPage),
+        0x69 => (ADC, Immediate),
+        0x6C => (JMP, Indirect),
+        0x6D => (ADC, Absolute),
+        0x71 => (ADC, IndirectIndexed),
+        0x75 => (ADC, ZeroPageX),
+        0x78 => (SEI, Implicit),
+        0x79 => (ADC, AbsoluteY),
+        0x7D => (ADC, AbsoluteX),
+        0x81 => (STA, IndexedIndirect),
+        0x85 => (STA, ZeroPage),
+        0x88 => (DEY, Implicit),
+        0x8A => (TXA, Implicit),
+        0x8D => (STA, Absolute),
+        0x90 => (BCC, Relative),
+        0x91 => (STA, IndirectIndexed),
+        0x95 => (STA, ZeroPageX),
+        0x98 => (TYA, Implicit),
+        0x99 => (STA, AbsoluteY),
+        0x9D => (STA, AbsoluteX),
+        0xA0 => (LDY, Immediate),
+        0xA1 => (LDA, IndexedIndirect),
+        0xA2 => (LDX, Immediate),
+        0xA4 => (LDY, ZeroPage),
+        0xA5 => (LDA, ZeroPage),
+        0xA6 => (LDX, ZeroPage),
+        0xA8 => (TAY, Implicit),
+        0xA9 => (LDA, Immediate),
+        0xAA => (TAX, Implicit),
+        0xAC => (LDY, Absolute),
+        0xAD => (LDA, Absolute),
+        0xAE => (LDX, Absolute),
+        0xB1 => (LDA, IndirectIndexed),
+        0xB4 => (LDY, ZeroPageX),
+        0xB5 => (LDA, ZeroPageX),
+        0xB6 => (LDX, ZeroPageY),
+        0xB8 => (CLV, Implicit),
+        0xB9 => (LDA, AbsoluteY),
+        0xBC => (LDY, AbsoluteX),
+        0xBD => (LDA, AbsoluteX),
+        0xBE => (LDX, AbsoluteY),
+        0xC8 => (INY, Implicit),
+        0xCA => (DEX, Implicit),
+        0xD8 => (CLD, Implicit),
+        0xE1 => (SBC, IndexedIndirect),
+        0xE5 => (SBC, ZeroPage),
+        0xE6 => (INC, ZeroPage),
+        0xE8 => (INX, Implicit),
+        0xE9 => (SBC, Immediate),
+        0xEA => (NOP, Implicit),
+        0xED => (SBC, Absolute),
+        0xEE => (INC, Absolute),
+        0xF1 => (SBC, IndirectIndexed),
+        0xF5 => (SBC, ZeroPageX),
+        0xF6 => (INC, ZeroPageX),
+        0xF8 => (SED, Implicit),
+        0xF9 => (SBC, AbsoluteY),
+        0xFD => (SBC, AbsoluteX),
+        0xFE => (INC, AbsoluteX),
+        _ => (Illegal(opcode), Implicit),
     }
 }
 
 #[cfg(test)]
 mod tests {
     use super::*;
+    use crate::bus::{CpuBus, ROM_SIZE, ROM_START};
+    use crate::assembler::assemble;
+    use crate::memory::Memory;
 
-    fn program(bytes: &[u8]) -> CPU {
-        CPU::new(Memory::with_program(bytes))
+    fn program(bytes: &[u8]) -> CPU<CpuBus> {
+        let bus = CpuBus::new(Memory::<ROM_SIZE>::with_program(bytes));
+        let mut cpu = CPU::new(bus);
+        cpu.pc = ROM_START;
+        cpu
     }
 
     #[test]
     fn test_0x00_brk() {
-        // TODO
+        let mut cpu = program(&assemble("BRK\nNOP"));
+        cpu.bus.write_u8(0xFFFE, 0x00);
+        cpu.bus.write_u8(0xFFFF, 0x02);
+        let start_pc = cpu.pc;
+        cpu.tick();
+        assert_eq!(cpu.pc, 0x0200);
+        assert!(cpu.sr.contains(Status::I));
+        let pushed_status = cpu.pull_u8();
+        assert!(Status::from_bits_truncate(pushed_status).contains(Status::B));
+        let pushed_pc = cpu.pull_u16();
+        assert_eq!(pushed_pc, start_pc.wrapping_add(2));
     }
 
     #[test]
     fn test_0x06_asl() {
         let mut cpu = program(&[0x06, 0x20]);
-        cpu.wram.write_u8(0x20, 0b0000_0001);
+        cpu.bus.write_u8(0x20, 0b0000_0001);
         cpu.tick();
-        assert_eq!(cpu.wram.read_u8(0x20), 0b0000_0010);
+        assert_eq!(cpu.bus.read_u8(0x20), 0b0000_0010);
         assert!(cpu.sr.is_empty());
     }
 
@@ -305,17 +701,17 @@ mod tests {
     #[test]
     fn test_0x06_asl_negative_flag() {
         let mut cpu = program(&[0x06, 0x20]);
-        cpu.wram.write_u8(0x20, 0x40);
+        cpu.bus.write_u8(0x20, 0x40);
         cpu.tick();
         // multiplies by 2
-        assert_eq!(cpu.wram.read_u8(0x20), 0x80);
+        assert_eq!(cpu.bus.read_u8(0x20), 0x80);
         assert!(cpu.sr.contains(Status::N));
     }
 
     #[test]
     fn test_0x06_asl_carry_flag() {
         let mut cpu = program(&[0x06, 0x20]);
-        cpu.wram.write_u8(0x20, 0b1000_0000);
+        cpu.bus.write_u8(0x20, 0b1000_0000);
         cpu.tick();
         assert!(cpu.sr.contains(Status::C));
     }
@@ -355,7 +751,7 @@ mod tests {
     #[test]
     fn test_0x25_and() {
         let mut cpu = program(&[0x25, 0x20]);
-        cpu.wram.write_u8(0x20, 0b1010);
+        cpu.bus.write_u8(0x20, 0b1010);
         cpu.acc = 0b1111;
         cpu.tick();
         assert_eq!(cpu.acc, 0b1010);
@@ -373,7 +769,7 @@ mod tests {
     #[test]
     fn test_0x25_and_negative_flag() {
         let mut cpu = program(&[0x25, 0x20]);
-        cpu.wram.write_u8(0x20, 0xFF);
+        cpu.bus.write_u8(0x20, 0xFF);
         cpu.acc = 0x80;
         cpu.tick();
         assert!(cpu.sr.contains(Status::N));
@@ -406,7 +802,7 @@ mod tests {
         let mut cpu = program(&[0x85, 0xFF]);
         cpu.acc = 0xFF;
         cpu.tick();
-        assert_eq!(cpu.wram.read_u8(0xFF), 0xFF);
+        assert_eq!(cpu.bus.read_u8(0xFF), 0xFF);
     }
 
     #[test]
@@ -449,7 +845,7 @@ mod tests {
     #[test]
     fn test_0x65_adc() {
         let mut cpu = program(&[0x65, 0x20]);
-        cpu.wram.write_u8(0x20, 0x40);
+        cpu.bus.write_u8(0x20, 0x40);
         cpu.acc = 0x04;
         cpu.tick();
         assert_eq!(cpu.acc, 0x44);
@@ -467,7 +863,7 @@ mod tests {
     #[test]
     fn test_0x65_adc_negative_flag() {
         let mut cpu = program(&[0x65, 0x20]);
-        cpu.wram.write_u8(0x20, 1);
+        cpu.bus.write_u8(0x20, 1);
         cpu.acc = 0x7F;
         cpu.tick();
         assert!(cpu.sr.contains(Status::N));
@@ -476,24 +872,116 @@ mod tests {
     #[test]
     fn test_0x65_adc_carry_flag() {
         let mut cpu = program(&[0x65, 0x20]);
-        cpu.wram.write_u8(0x20, 1);
+        cpu.bus.write_u8(0x20, 1);
+        cpu.acc = 0xFF;
+        cpu.tick();
+        assert!(cpu.sr.contains(Status::C));
+    }
+
+    #[test]
+    fn test_0x65_adc_overflow_flag() {
+        // 0x7F + 1 overflows into a negative result
+        let mut cpu = program(&[0x65, 0x20]);
+        cpu.bus.write_u8(0x20, 1);
+        cpu.acc = 0x7F;
+        cpu.tick();
+        assert!(cpu.sr.contains(Status::V));
+    }
+
+    #[test]
+    fn test_0x65_adc_no_overflow_on_carry_only() {
+        // 0xFF + 1 wraps to zero but doesn't change the sign, so no
+        // overflow even though carry is set
+        let mut cpu = program(&[0x65, 0x20]);
+        cpu.bus.write_u8(0x20, 1);
         cpu.acc = 0xFF;
         cpu.tick();
+        assert!(!cpu.sr.contains(Status::V));
+    }
+
+    #[test]
+    fn test_0x65_adc_decimal_mode() {
+        let mut cpu = program(&[0x65, 0x20]);
+        cpu.bus.write_u8(0x20, 0x01);
+        cpu.acc = 0x09;
+        cpu.sr.set(Status::D, true);
+        cpu.tick();
+        assert_eq!(cpu.acc, 0x10);
+        assert!(!cpu.sr.contains(Status::C));
+    }
+
+    #[test]
+    fn test_0x65_adc_decimal_mode_carry() {
+        let mut cpu = program(&[0x65, 0x20]);
+        cpu.bus.write_u8(0x20, 0x99);
+        cpu.acc = 0x99;
+        cpu.sr.set(Status::D, true);
+        cpu.tick();
+        assert_eq!(cpu.acc, 0x98);
+        assert!(cpu.sr.contains(Status::C));
+    }
+
+    #[test]
+    fn test_0xe5_sbc() {
+        let mut cpu = program(&[0xE5, 0x20]);
+        cpu.bus.write_u8(0x20, 0x10);
+        cpu.acc = 0x50;
+        cpu.sr.set(Status::C, true); // no borrow
+        cpu.tick();
+        assert_eq!(cpu.acc, 0x40);
+        assert!(cpu.sr.contains(Status::C));
+    }
+
+    #[test]
+    fn test_0xe5_sbc_with_borrow() {
+        let mut cpu = program(&[0xE5, 0x20]);
+        cpu.bus.write_u8(0x20, 0x01);
+        cpu.acc = 0x00;
+        cpu.sr.set(Status::C, true); // no borrow
+        cpu.tick();
+        assert_eq!(cpu.acc, 0xFF);
+        // borrow occurred, so carry is cleared
+        assert!(!cpu.sr.contains(Status::C));
+    }
+
+    #[test]
+    fn test_0xe5_sbc_decimal_mode() {
+        let mut cpu = program(&[0xE5, 0x20]);
+        cpu.bus.write_u8(0x20, 0x01);
+        cpu.acc = 0x10;
+        cpu.sr.set(Status::D, true);
+        cpu.sr.set(Status::C, true); // no borrow
+        cpu.tick();
+        // $10 - $01 in BCD is $09
+        assert_eq!(cpu.acc, 0x09);
         assert!(cpu.sr.contains(Status::C));
     }
 
+    #[test]
+    fn test_0xe5_sbc_decimal_mode_with_borrow() {
+        let mut cpu = program(&[0xE5, 0x20]);
+        cpu.bus.write_u8(0x20, 0x01);
+        cpu.acc = 0x00;
+        cpu.sr.set(Status::D, true);
+        cpu.sr.set(Status::C, true); // no borrow
+        cpu.tick();
+        // $00 - $01 in BCD borrows out to $99
+        assert_eq!(cpu.acc, 0x99);
+        assert!(!cpu.sr.contains(Status::C));
+    }
+
     #[test]
     fn test_0xe6_inc() {
         let mut cpu = program(&[0xE6, 0x20]);
-        cpu.wram.write_u8(0x20, 0x40);
+        cpu.bus.write_u8(0x20, 0x40);
         cpu.tick();
-        assert_eq!(cpu.wram.read_u8(0x20), 0x41);
+        assert_eq!(cpu.bus.read_u8(0x20), 0x41);
     }
 
     #[test]
     fn test_0xe6_inc_zero_flag() {
         let mut cpu = program(&[0xE6, 0x20]);
-        cpu.wram.write_u8(0x20, 0xFF);
+        cpu.bus.write_u8(0x20, 0xFF);
         cpu.tick();
         assert!(cpu.sr.contains(Status::Z));
     }
@@ -501,14 +989,15 @@ mod tests {
     #[test]
     fn test_0xe6_inc_negative_flag() {
         let mut cpu = program(&[0xE6, 0x20]);
-        cpu.wram.write_u8(0x20, 0x7F);
+        cpu.bus.write_u8(0x20, 0x7F);
         cpu.tick();
         assert!(cpu.sr.contains(Status::N));
     }
 
     #[test]
     fn test_0xa6_ldx() {
-        let mut cpu = program(&[0xA6, 0x40]);
+        let mut cpu = program(&[0xA6, 0x20]);
+        cpu.bus.write_u8(0x20, 0x40);
         cpu.tick();
         assert_eq!(cpu.x, 0x40);
         assert!(cpu.sr.is_empty());
@@ -516,21 +1005,23 @@ mod tests {
 
     #[test]
     fn test_0xa6_ldx_zero_flag() {
-        let mut cpu = program(&[0xA6, 0]);
+        let mut cpu = program(&[0xA6, 0x20]);
         cpu.tick();
         assert!(cpu.sr.contains(Status::Z));
     }
 
     #[test]
     fn test_0xa6_ldx_negative_flag() {
-        let mut cpu = program(&[0xA6, 0x80]);
+        let mut cpu = program(&[0xA6, 0x20]);
+        cpu.bus.write_u8(0x20, 0x80);
         cpu.tick();
         assert!(cpu.sr.contains(Status::N));
     }
 
     #[test]
     fn test_0xa4_ldy() {
-        let mut cpu = program(&[0xA4, 0x40]);
+        let mut cpu = program(&[0xA4, 0x20]);
+        cpu.bus.write_u8(0x20, 0x40);
         cpu.tick();
         assert_eq!(cpu.y, 0x40);
         assert!(cpu.sr.is_empty());
@@ -538,14 +1029,15 @@ mod tests {
 
     #[test]
     fn test_0xa4_ldy_zero_flag() {
-        let mut cpu = program(&[0xA4, 0x00]);
+        let mut cpu = program(&[0xA4, 0x20]);
         cpu.tick();
         assert!(cpu.sr.contains(Status::Z));
     }
 
     #[test]
     fn test_0xa4_ldy_negative_flag() {
-        let mut cpu = program(&[0xA4, 0x80]);
+        let mut cpu = program(&[0xA4, 0x20]);
+        cpu.bus.write_u8(0x20, 0x80);
         cpu.tick();
         assert!(cpu.sr.contains(Status::N));
     }
@@ -679,6 +1171,16 @@ mod tests {
         // as long as we don't panic, we're good
     }
 
+    #[test]
+    fn test_fetch_wraps_at_top_of_address_space() {
+        let mut cpu = program(&[0xEA]);
+        cpu.bus.write_u8(0xFFFF, 0xEA); // NOP
+        cpu.pc = 0xFFFF;
+        cpu.tick();
+        // PC wraps from $FFFF back to $0000 instead of panicking
+        assert_eq!(cpu.pc, 0x0000);
+    }
+
     #[test]
     fn test_0x38_sec() {
         let mut cpu = program(&[0x38]);
@@ -814,4 +1316,339 @@ mod tests {
         cpu.tick();
         assert!(cpu.sr.contains(Status::N));
     }
+
+    #[test]
+    fn test_0xad_lda_absolute() {
+        let mut cpu = program(&[0xAD, 0x00, 0x02]);
+        cpu.bus.write_u8(0x0200, 0x40);
+        cpu.tick();
+        assert_eq!(cpu.acc, 0x40);
+    }
+
+    #[test]
+    fn test_0xbd_lda_absolute_x() {
+        let mut cpu = program(&[0xBD, 0x00, 0x02]);
+        cpu.x = 0x05;
+        cpu.bus.write_u8(0x0205, 0x40);
+        cpu.tick();
+        assert_eq!(cpu.acc, 0x40);
+    }
+
+    #[test]
+    fn test_0xb9_lda_absolute_y() {
+        let mut cpu = program(&[0xB9, 0x00, 0x02]);
+        cpu.y = 0x05;
+        cpu.bus.write_u8(0x0205, 0x40);
+        cpu.tick();
+        assert_eq!(cpu.acc, 0x40);
+    }
+
+    #[test]
+    fn test_0xb5_lda_zero_page_x() {
+        let mut cpu = program(&[0xB5, 0xFE]);
+        cpu.x = 0x03;
+        cpu.bus.write_u8(0x01, 0x40);
+        cpu.tick();
+        assert_eq!(cpu.acc, 0x40);
+    }
+
+    #[test]
+    fn test_0xa1_lda_indexed_indirect() {
+        let mut cpu = program(&[0xA1, 0x20]);
+        cpu.x = 0x04;
+        cpu.bus.write_u8(0x24, 0x00);
+        cpu.bus.write_u8(0x25, 0x02);
+        cpu.bus.write_u8(0x0200, 0x40);
+        cpu.tick();
+        assert_eq!(cpu.acc, 0x40);
+    }
+
+    #[test]
+    fn test_0xa1_lda_indexed_indirect_zero_page_wrap() {
+        let mut cpu = program(&[0xA1, 0xFF]);
+        cpu.x = 0x01;
+        // pointer wraps from $100 back to $00, not into page one
+        cpu.bus.write_u8(0x00, 0x00);
+        cpu.bus.write_u8(0x01, 0x02);
+        cpu.bus.write_u8(0x0200, 0x40);
+        cpu.tick();
+        assert_eq!(cpu.acc, 0x40);
+    }
+
+    #[test]
+    fn test_0xb1_lda_indirect_indexed() {
+        let mut cpu = program(&[0xB1, 0x20]);
+        cpu.y = 0x04;
+        cpu.bus.write_u8(0x20, 0x00);
+        cpu.bus.write_u8(0x21, 0x02);
+        cpu.bus.write_u8(0x0204, 0x40);
+        cpu.tick();
+        assert_eq!(cpu.acc, 0x40);
+    }
+
+    #[test]
+    fn test_0x90_bcc_relative_backwards() {
+        let mut cpu = program(&[
+            0xC8,       // INY (skipped over on first pass)
+            0x90, 0xFD, // BCC -3, back to the INY above
+        ]);
+        cpu.pc = cpu.pc.wrapping_add(1);
+        cpu.sr.set(Status::C, true);
+        cpu.tick();
+        assert_eq!(cpu.pc, ROM_START);
+    }
+
+    #[test]
+    fn test_0x4c_jmp_absolute() {
+        let mut cpu = program(&[0x4C, 0x00, 0x02]);
+        cpu.tick();
+        assert_eq!(cpu.pc, 0x0200);
+    }
+
+    #[test]
+    fn test_0x6c_jmp_indirect_page_boundary_bug() {
+        // the pointer sits at the end of a page: the real 6502 fetches
+        // the high byte from the start of the *same* page instead of
+        // carrying into the next one
+        let mut cpu = program(&[0x6C, 0xFF, 0x10]);
+        cpu.bus.write_u8(0x10FF, 0x34);
+        cpu.bus.write_u8(0x1000, 0x12);
+        cpu.bus.write_u8(0x1100, 0x99); // should never be read
+        cpu.tick();
+        assert_eq!(cpu.pc, 0x1234);
+    }
+
+    #[test]
+    fn test_0x48_pha_0x68_pla() {
+        let mut cpu = program(&assemble("PHA\nPLA"));
+        let sp = cpu.sp;
+        cpu.acc = 0x42;
+        cpu.tick(); // PHA
+        assert_eq!(cpu.sp, sp.wrapping_sub(1));
+        cpu.acc = 0;
+        cpu.tick(); // PLA
+        assert_eq!(cpu.acc, 0x42);
+        assert_eq!(cpu.sp, sp);
+    }
+
+    #[test]
+    fn test_0x08_php_0x28_plp() {
+        let mut cpu = program(&assemble("PHP\nPLP"));
+        cpu.sr.set(Status::C, true);
+        cpu.sr.set(Status::N, true);
+        cpu.tick(); // PHP
+        cpu.sr = Status::empty();
+        cpu.tick(); // PLP
+        assert!(cpu.sr.contains(Status::C));
+        assert!(cpu.sr.contains(Status::N));
+        // B isn't a real status bit, only ever lives on the stack copy
+        assert!(!cpu.sr.contains(Status::B));
+    }
+
+    #[test]
+    fn test_0x20_jsr_0x60_rts() {
+        // JSR $0005: an address within RAM for this test, not the program
+        // itself; the NOPs are padding never executed, RTS is jumped to
+        // directly below
+        let mut cpu = program(&assemble(
+            "JSR $0005
+             NOP
+             NOP
+             RTS",
+        ));
+        let jsr_return = cpu.pc.wrapping_add(3);
+        cpu.tick(); // JSR
+        assert_eq!(cpu.pc, 0x0005);
+        cpu.pc = ROM_START.wrapping_add(5);
+        cpu.tick(); // RTS
+        assert_eq!(cpu.pc, jsr_return);
+    }
+
+    #[test]
+    fn test_0x40_rti() {
+        let mut cpu = program(&assemble("RTI"));
+        cpu.push_u16(0x1234);
+        cpu.push_u8(Status::C.bits());
+        cpu.tick();
+        assert_eq!(cpu.pc, 0x1234);
+        assert!(cpu.sr.contains(Status::C));
+    }
+
+    #[test]
+    fn test_nmi() {
+        let mut cpu = program(&assemble("NOP"));
+        cpu.bus.write_u8(0xFFFA, 0x00);
+        cpu.bus.write_u8(0xFFFB, 0x03);
+        let start_pc = cpu.pc;
+        cpu.nmi();
+        assert_eq!(cpu.pc, 0x0300);
+        assert!(cpu.sr.contains(Status::I));
+        cpu.pull_u8(); // status
+        assert_eq!(cpu.pull_u16(), start_pc);
+    }
+
+    #[test]
+    fn test_irq_ignored_while_i_flag_set() {
+        let mut cpu = program(&assemble("NOP"));
+        cpu.bus.write_u8(0xFFFE, 0x00);
+        cpu.bus.write_u8(0xFFFF, 0x03);
+        let start_pc = cpu.pc;
+        cpu.sr.set(Status::I, true);
+        cpu.irq();
+        assert_eq!(cpu.pc, start_pc);
+    }
+
+    #[test]
+    fn test_irq_runs_when_i_flag_clear() {
+        let mut cpu = program(&assemble("NOP"));
+        cpu.bus.write_u8(0xFFFE, 0x00);
+        cpu.bus.write_u8(0xFFFF, 0x03);
+        cpu.irq();
+        assert_eq!(cpu.pc, 0x0300);
+        assert!(cpu.sr.contains(Status::I));
+    }
+
+    #[test]
+    fn test_reset() {
+        let mut cpu = program(&assemble("NOP"));
+        cpu.bus.write_u8(0xFFFC, 0x00);
+        cpu.bus.write_u8(0xFFFD, 0x04);
+        cpu.reset();
+        assert_eq!(cpu.pc, 0x0400);
+        assert!(cpu.sr.contains(Status::I));
+    }
+
+    #[test]
+    fn test_trace_immediate() {
+        let cpu = program(&[0xA9, 0x42]);
+        assert_eq!(
+            cpu.trace(),
+            "F100  A9 42     LDA #$42                        A:00 X:00 Y:00 P:00 SP:FD"
+        );
+    }
+
+    #[test]
+    fn test_trace_zero_page_shows_resolved_value() {
+        let mut cpu = program(&[0xA5, 0x10]);
+        cpu.bus.write_u8(0x10, 0x37);
+        assert_eq!(
+            cpu.trace(),
+            "F100  A5 10     LDA $10 = 37                    A:00 X:00 Y:00 P:00 SP:FD"
+        );
+    }
+
+    #[test]
+    fn test_trace_absolute_x_shows_effective_address_and_value() {
+        let mut cpu = program(&[0xBD, 0x00, 0x02]);
+        cpu.x = 0x01;
+        cpu.bus.write_u8(0x0201, 0x99);
+        assert_eq!(
+            cpu.trace(),
+            "F100  BD 00 02  LDA $0200,X @ 0201 = 99         A:00 X:01 Y:00 P:00 SP:FD"
+        );
+    }
+
+    #[test]
+    fn test_trace_zero_page_x() {
+        let mut cpu = program(&[0xB5, 0x10]);
+        cpu.x = 0x01;
+        cpu.bus.write_u8(0x11, 0x37);
+        assert_eq!(
+            cpu.trace(),
+            "F100  B5 10     LDA $10,X @ 11 = 37             A:00 X:01 Y:00 P:00 SP:FD"
+        );
+    }
+
+    #[test]
+    fn test_trace_zero_page_y() {
+        let mut cpu = program(&[0xB6, 0x10]);
+        cpu.y = 0x01;
+        cpu.bus.write_u8(0x11, 0x37);
+        assert_eq!(
+            cpu.trace(),
+            "F100  B6 10     LDX $10,Y @ 11 = 37             A:00 X:00 Y:01 P:00 SP:FD"
+        );
+    }
+
+    #[test]
+    fn test_trace_absolute_y() {
+        let mut cpu = program(&[0xB9, 0x00, 0x02]);
+        cpu.y = 0x01;
+        cpu.bus.write_u8(0x0201, 0x99);
+        assert_eq!(
+            cpu.trace(),
+            "F100  B9 00 02  LDA $0200,Y @ 0201 = 99         A:00 X:00 Y:01 P:00 SP:FD"
+        );
+    }
+
+    #[test]
+    fn test_trace_relative() {
+        let cpu = program(&[0x90, 0x02]);
+        assert_eq!(
+            cpu.trace(),
+            "F100  90 02     BCC $F104                       A:00 X:00 Y:00 P:00 SP:FD"
+        );
+    }
+
+    #[test]
+    fn test_trace_indirect() {
+        let mut cpu = program(&[0x6C, 0x34, 0x12]);
+        cpu.bus.write_u8(0x1234, 0x78);
+        cpu.bus.write_u8(0x1235, 0x56);
+        assert_eq!(
+            cpu.trace(),
+            "F100  6C 34 12  JMP ($1234) = 5678              A:00 X:00 Y:00 P:00 SP:FD"
+        );
+    }
+
+    #[test]
+    fn test_trace_indexed_indirect() {
+        let mut cpu = program(&[0xA1, 0x20]);
+        cpu.x = 0x04;
+        cpu.bus.write_u8(0x24, 0x00);
+        cpu.bus.write_u8(0x25, 0x02);
+        cpu.bus.write_u8(0x0200, 0x40);
+        assert_eq!(
+            cpu.trace(),
+            "F100  A1 20     LDA ($20,X) @ 0200 = 40         A:00 X:04 Y:00 P:00 SP:FD"
+        );
+    }
+
+    #[test]
+    fn test_trace_indirect_indexed() {
+        let mut cpu = program(&[0xB1, 0x20]);
+        cpu.y = 0x04;
+        cpu.bus.write_u8(0x20, 0x00);
+        cpu.bus.write_u8(0x21, 0x02);
+        cpu.bus.write_u8(0x0204, 0x40);
+        assert_eq!(
+            cpu.trace(),
+            "F100  B1 20     LDA ($20),Y @ 0204 = 40         A:00 X:00 Y:04 P:00 SP:FD"
+        );
+    }
+
+    #[test]
+    fn test_trace_accumulator() {
+        let cpu = program(&[0x0A]);
+        assert_eq!(
+            cpu.trace(),
+            "F100  0A        ASL A                           A:00 X:00 Y:00 P:00 SP:FD"
+        );
+    }
+
+    #[test]
+    fn test_trace_implicit() {
+        let cpu = program(&[0xEA]);
+        assert_eq!(
+            cpu.trace(),
+            "F100  EA        NOP                             A:00 X:00 Y:00 P:00 SP:FD"
+        );
+    }
+
+    #[test]
+    fn test_trace_does_not_advance_pc() {
+        let cpu = program(&[0xEA]);
+        cpu.trace();
+        assert_eq!(cpu.pc, ROM_START);
+    }
 }
@@ -0,0 +1,141 @@
+//! Disassembly support shared by [`crate::cpu::CPU::trace`]. Everything
+//! here reads the bus but never mutates CPU state, so it can safely peek
+//! at the instruction about to run without disturbing `tick`.
+
+use crate::bus::Bus;
+use crate::cpu::{read_indirect_u16, AddressingMode, Instruction};
+
+/// The 3-letter mnemonic for an instruction, as used in trace output.
+pub(crate) fn mnemonic(inst: &Instruction) -> &'static str {
+    use Instruction::*;
+    match inst {
+        ADC => "ADC",
+        AND => "AND",
+        ASL => "ASL",
+        BRK => "BRK",
+        BCC => "BCC",
+        CLC => "CLC",
+        CLD => "CLD",
+        CLI => "CLI",
+        CLV => "CLV",
+        DEX => "DEX",
+        DEY => "DEY",
+        INC => "INC",
+        INX => "INX",
+        INY => "INY",
+        JMP => "JMP",
+        JSR => "JSR",
+        LDA => "LDA",
+        LDX => "LDX",
+        LDY => "LDY",
+        NOP => "NOP",
+        PHA => "PHA",
+        PHP => "PHP",
+        PLA => "PLA",
+        PLP => "PLP",
+        RTI => "RTI",
+        RTS => "RTS",
+        SBC => "SBC",
+        SEC => "SEC",
+        SED => "SED",
+        SEI => "SEI",
+        STA => "STA",
+        TAX => "TAX",
+        TAY => "TAY",
+        TXA => "TXA",
+        TYA => "TYA",
+        Illegal(_) => "???",
+    }
+}
+
+/// How many operand bytes follow the opcode byte for `mode`.
+pub(crate) fn operand_len(mode: &AddressingMode) -> u16 {
+    use AddressingMode::*;
+    match mode {
+        Implicit | Accumulator => 0,
+        Immediate | ZeroPage | ZeroPageX | ZeroPageY | Relative | IndexedIndirect
+        | IndirectIndexed => 1,
+        Absolute | AbsoluteX | AbsoluteY | Indirect => 2,
+    }
+}
+
+/// Formats the operand the way nestest trace logs do: the addressing-mode
+/// syntax, plus (for indexed/indirect modes) the effective address and the
+/// value found there, e.g. `$10,X @ 20 = 05`.
+///
+/// This mirrors `CPU::resolve`'s address arithmetic but only reads memory,
+/// never steps the program counter. The indirect/indexed-indirect/
+/// indirect-indexed effective-address math (including the JMP indirect
+/// page-boundary bug) is shared with `resolve` via
+/// [`crate::cpu::read_indirect_u16`] so the two can't silently desync.
+pub(crate) fn format_operand(
+    mode: &AddressingMode,
+    pc_after_operand: u16,
+    operand: &[u8],
+    x: u8,
+    y: u8,
+    bus: &impl Bus,
+) -> String {
+    use AddressingMode::*;
+    match mode {
+        Implicit => String::new(),
+        Accumulator => "A".to_string(),
+        Immediate => format!("#${:02X}", operand[0]),
+        ZeroPage => {
+            let addr = operand[0] as u16;
+            format!("${:02X} = {:02X}", addr, bus.read_u8(addr))
+        }
+        ZeroPageX => {
+            let addr = operand[0].wrapping_add(x) as u16;
+            format!("${:02X},X @ {:02X} = {:02X}", operand[0], addr, bus.read_u8(addr))
+        }
+        ZeroPageY => {
+            let addr = operand[0].wrapping_add(y) as u16;
+            format!("${:02X},Y @ {:02X} = {:02X}", operand[0], addr, bus.read_u8(addr))
+        }
+        Relative => {
+            let offset = operand[0] as i8;
+            let target = pc_after_operand.wrapping_add(offset as u16);
+            format!("${:04X}", target)
+        }
+        Absolute => {
+            let addr = u16::from_le_bytes([operand[0], operand[1]]);
+            format!("${:04X}", addr)
+        }
+        AbsoluteX => {
+            let base = u16::from_le_bytes([operand[0], operand[1]]);
+            let addr = base.wrapping_add(x as u16);
+            format!("${:04X},X @ {:04X} = {:02X}", base, addr, bus.read_u8(addr))
+        }
+        AbsoluteY => {
+            let base = u16::from_le_bytes([operand[0], operand[1]]);
+            let addr = base.wrapping_add(y as u16);
+            format!("${:04X},Y @ {:04X} = {:02X}", base, addr, bus.read_u8(addr))
+        }
+        Indirect => {
+            let ptr = u16::from_le_bytes([operand[0], operand[1]]);
+            let target = read_indirect_u16(bus, ptr);
+            format!("(${:04X}) = {:04X}", ptr, target)
+        }
+        IndexedIndirect => {
+            let ptr = operand[0].wrapping_add(x);
+            let addr = read_indirect_u16(bus, ptr as u16);
+            format!(
+                "(${:02X},X) @ {:04X} = {:02X}",
+                operand[0],
+                addr,
+                bus.read_u8(addr)
+            )
+        }
+        IndirectIndexed => {
+            let base = read_indirect_u16(bus, operand[0] as u16);
+            let addr = base.wrapping_add(y as u16);
+            format!(
+                "(${:02X}),Y @ {:04X} = {:02X}",
+                operand[0],
+                addr,
+                bus.read_u8(addr)
+            )
+        }
+    }
+}
@@ -0,0 +1,5 @@
+pub mod assembler;
+pub mod bus;
+pub mod cpu;
+mod disasm;
+pub mod memory;
@@ -0,0 +1,36 @@
+/// A fixed-size block of bytes, addressed by a 16-bit address.
+///
+/// `Memory` has no opinion on where in the address space it lives; that's
+/// up to whatever wires it up (see [`crate::bus`]).
+#[derive(Debug)]
+pub struct Memory<const N: usize> {
+    data: [u8; N],
+}
+
+impl<const N: usize> Memory<N> {
+    pub fn new() -> Memory<N> {
+        Memory { data: [0; N] }
+    }
+
+    /// Build memory with `bytes` loaded at the start, useful for tests and
+    /// small example programs.
+    pub fn with_program(bytes: &[u8]) -> Memory<N> {
+        let mut data = [0; N];
+        data[..bytes.len()].copy_from_slice(bytes);
+        Memory { data }
+    }
+
+    pub fn read_u8(&self, addr: u16) -> u8 {
+        self.data[addr as usize % N]
+    }
+
+    pub fn write_u8(&mut self, addr: u16, val: u8) {
+        self.data[addr as usize % N] = val;
+    }
+}
+
+impl<const N: usize> Default for Memory<N> {
+    fn default() -> Self {
+        Memory::new()
+    }
+}
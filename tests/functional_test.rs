@@ -0,0 +1,101 @@
+//! Runs Klaus Dormann's well-known `6502_functional_test` binary against
+//! whatever of the instruction set `cpu::decode` currently implements.
+//!
+//! `decode` is still missing entire instruction families the suite
+//! exercises (e.g. `CMP`/`CPX`/`CPY`, most branches, `EOR`/`ORA`,
+//! `LSR`/`ROL`/`ROR`, `BIT`, `DEC`), so this doesn't yet validate the full
+//! instruction set the way the real test ROM is designed to — `tick` panics
+//! on `Illegal(opcode)` the moment the ROM reaches one of those. Once
+//! `decode`'s table is closer to complete, this harness will exercise far
+//! more addressing-mode combinations than the hand-written unit tests in
+//! `cpu.rs`.
+//!
+//! The ROM isn't checked into this repo. Point `NES_FUNCTIONAL_TEST_ROM`
+//! at a copy of `6502_functional_test.bin` (from
+//! https://github.com/Klaus2m5/6502_65C02_functional_tests), or drop it
+//! at `tests/roms/6502_functional_test.bin`, to run this test; otherwise
+//! it skips cleanly.
+
+use nes::bus::Bus;
+use nes::cpu::CPU;
+use std::env;
+use std::fs;
+use std::path::PathBuf;
+
+// The test binary is a full 64KB image copied in at $0000 whose code
+// starts at $0400 (LOAD_ADDRESS, written into the reset vector below). On
+// success it branches
+// to itself forever at this address instead of writing to some magic
+// port. A trap anywhere else means a regression in the instruction it was
+// exercising when it got stuck.
+const LOAD_ADDRESS: u16 = 0x0400;
+const SUCCESS_PC: u16 = 0x3469;
+
+/// A flat, unmirrored 64KB address space. The functional test ROM pokes
+/// at its own code and data all over memory, so it needs the whole
+/// space writable, unlike the NES's real (much smaller) CPU map.
+struct FlatBus {
+    mem: [u8; 0x10000],
+}
+
+impl FlatBus {
+    fn new() -> FlatBus {
+        FlatBus { mem: [0; 0x10000] }
+    }
+}
+
+impl Bus for FlatBus {
+    fn read_u8(&self, addr: u16) -> u8 {
+        self.mem[addr as usize]
+    }
+
+    fn write_u8(&mut self, addr: u16, val: u8) {
+        self.mem[addr as usize] = val;
+    }
+}
+
+fn rom_path() -> Option<PathBuf> {
+    if let Ok(path) = env::var("NES_FUNCTIONAL_TEST_ROM") {
+        return Some(PathBuf::from(path));
+    }
+    let default = PathBuf::from("tests/roms/6502_functional_test.bin");
+    default.exists().then_some(default)
+}
+
+#[test]
+fn runs_klaus_dormann_functional_test() {
+    let Some(path) = rom_path() else {
+        eprintln!(
+            "skipping functional test: set NES_FUNCTIONAL_TEST_ROM or place the ROM at \
+             tests/roms/6502_functional_test.bin"
+        );
+        return;
+    };
+    let program = fs::read(&path).expect("failed to read functional test ROM");
+
+    let mut bus = FlatBus::new();
+    bus.mem[..program.len()].copy_from_slice(&program);
+    bus.write_u8(0xFFFC, LOAD_ADDRESS as u8);
+    bus.write_u8(0xFFFD, (LOAD_ADDRESS >> 8) as u8);
+
+    let mut cpu = CPU::new(bus);
+    cpu.reset();
+
+    let mut last_pc = None;
+    loop {
+        cpu.tick();
+        let pc = cpu.pc();
+        if last_pc == Some(pc) {
+            // the program counter stopped advancing: either we reached
+            // the documented success trap, or a test case failed and
+            // looped on itself instead
+            assert_eq!(
+                pc, SUCCESS_PC,
+                "trapped at ${:04X} instead of the documented success address ${:04X}",
+                pc, SUCCESS_PC
+            );
+            break;
+        }
+        last_pc = Some(pc);
+    }
+}